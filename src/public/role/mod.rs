@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+/// Roles allow you to manage a set of named authorities and associate users to that role
+/// instead of relying on the legacy `Admin`/`Privileged`/`Participant` strings on `User.role`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Role {
+    /// The domo role id
+    pub id: Option<u32>,
+
+    /// Name of the role
+    pub name: Option<String>,
+
+    /// Description of the role
+    pub description: Option<String>,
+
+    /// Authorities granted to users associated with this role
+    pub authorities: Vec<String>,
+}
+
+impl Role {
+    pub fn new() -> Self {
+        Role {
+            id: None,
+            name: None,
+            description: None,
+            authorities: Vec::new(),
+        }
+    }
+    pub fn template() -> Self {
+        Role {
+            id: Some(0),
+            name: Some(String::from("Role Name")),
+            description: Some(String::from("Description")),
+            authorities: vec![String::from("dashboard.create")],
+        }
+    }
+}
+
+/// Role API methods
+/// Uses the form method_object
+impl super::Client {
+    /// Get a list of custom roles.
+    pub fn get_roles(&self) -> Result<Vec<Role>, Box<dyn Error>> {
+        let at = self.get_access_token("user")?;
+        Ok(self
+            .client
+            .get(&format!("{}{}", self.host, "/v1/roles"))
+            .header("Authorization", at)
+            .send()?
+            .error_for_status()?
+            .json()?)
+    }
+
+    /// Retrieves the details of an existing role.
+    pub fn get_role(&self, id: &str) -> Result<Role, Box<dyn Error>> {
+        let at = self.get_access_token("user")?;
+        Ok(self
+            .client
+            .get(&format!("{}{}{}", self.host, "/v1/roles/", id))
+            .header("Authorization", at)
+            .send()?
+            .error_for_status()?
+            .json()?)
+    }
+
+    /// Creates a new custom role in your Domo instance.
+    pub fn post_role(&self, role: Role) -> Result<Role, Box<dyn Error>> {
+        let at = self.get_access_token("user")?;
+        Ok(self
+            .client
+            .post(&format!("{}{}", self.host, "/v1/roles"))
+            .header("Authorization", at)
+            .json(&role)
+            .send()?
+            .error_for_status()?
+            .json()?)
+    }
+
+    /// Updates the specified role's name, description, and/or authorities.
+    pub fn put_role(&self, id: &str, role: Role) -> Result<Role, Box<dyn Error>> {
+        let at = self.get_access_token("user")?;
+        Ok(self
+            .client
+            .put(&format!("{}{}{}", self.host, "/v1/roles/", id))
+            .header("Authorization", at)
+            .json(&role)
+            .send()?
+            .error_for_status()?
+            .json()?)
+    }
+
+    /// Permanently deletes a custom role from your Domo instance.
+    /// This is destructive and cannot be reversed.
+    pub fn delete_role(&self, id: &str) -> Result<(), Box<dyn Error>> {
+        let at = self.get_access_token("user")?;
+        self.client
+            .delete(&format!("{}{}{}", self.host, "/v1/roles/", id))
+            .header("Authorization", at)
+            .send()?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Get the ids of the users associated with a role.
+    pub fn get_role_users(&self, id: &str) -> Result<Vec<u32>, Box<dyn Error>> {
+        let at = self.get_access_token("user")?;
+        Ok(self
+            .client
+            .get(&format!("{}{}{}{}", self.host, "/v1/roles/", id, "/users"))
+            .header("Authorization", at)
+            .send()?
+            .error_for_status()?
+            .json()?)
+    }
+
+    /// Associates the given users with a role, granting them its authorities.
+    pub fn add_role_users(&self, id: &str, user_ids: &[u32]) -> Result<(), Box<dyn Error>> {
+        let at = self.get_access_token("user")?;
+        self.client
+            .put(&format!("{}{}{}{}", self.host, "/v1/roles/", id, "/users"))
+            .header("Authorization", at)
+            .json(user_ids)
+            .send()?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Removes the given users from a role.
+    pub fn remove_role_users(&self, id: &str, user_ids: &[u32]) -> Result<(), Box<dyn Error>> {
+        let at = self.get_access_token("user")?;
+        self.client
+            .delete(&format!("{}{}{}{}", self.host, "/v1/roles/", id, "/users"))
+            .header("Authorization", at)
+            .json(user_ids)
+            .send()?
+            .error_for_status()?;
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::error::Error;
 
 /// User objects allow you to manage a user and the user’s attributes such as a department, phone number, employee number, email, and username. The API allows you to create, delete, retrieve a user or a list of users, and update user information
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 #[serde(default, rename_all = "camelCase")]
 pub struct User {
     /// The domo user id
@@ -41,6 +41,18 @@ pub struct User {
 
     /// If the user ID is related to a user that has been deleted, a subset of the user information will be returned, including a deleted property, which will be true.
     pub deleted: Option<bool>,
+
+    /// Stable identifier of the corresponding record in an external directory
+    /// (e.g. an IdP or HR system), used as the join point for `Client::sync_users`
+    /// instead of the internal Domo id.
+    pub external_id: Option<String>,
+
+    /// Whether the user's account is enabled. Use `Client::enable_user`/
+    /// `Client::disable_user` to flip this without providing every other field.
+    pub enabled: Option<bool>,
+
+    /// Epoch seconds at which the account expires and access is suspended.
+    pub expire: Option<i64>,
 }
 
 impl User {
@@ -58,6 +70,9 @@ impl User {
             locale: None,
             role: None,
             deleted: None,
+            external_id: None,
+            enabled: None,
+            expire: None,
         }
     }
     pub fn template() -> Self {
@@ -74,7 +89,91 @@ impl User {
             locale: Some(String::from("en-US")),
             role: Some(String::from("Admin - Match roles defined in instance")),
             deleted: Some(false),
+            external_id: Some(String::from("external-directory-id")),
+            enabled: Some(true),
+            expire: None,
+        }
+    }
+}
+
+/// Result of reconciling a desired roster against the current one with
+/// `Client::sync_users`, reported as the ids affected by each kind of change.
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub created: Vec<u32>,
+    pub updated: Vec<u32>,
+    pub deleted: Vec<u32>,
+    pub unchanged: Vec<u32>,
+}
+
+/// Per-record outcome of a `*_users_bulk` call: each input is attempted
+/// independently, so one bad record doesn't abort the whole batch.
+#[derive(Debug, Default)]
+pub struct BulkResult {
+    pub succeeded: Vec<User>,
+    pub failed: Vec<(User, String)>,
+}
+
+/// A field the desired record leaves unset (`None`) is "don't care" rather
+/// than "must be empty", since directory-sourced records typically only
+/// populate the fields they own.
+fn field_matches<T: PartialEq>(existing: &Option<T>, want: &Option<T>) -> bool {
+    match want {
+        Some(want) => existing.as_ref() == Some(want),
+        None => true,
+    }
+}
+
+/// Compares `existing` (the current Domo record) against `want` (the
+/// desired-state record) over the directory-owned fields only, ignoring `id`
+/// and the Domo-managed lifecycle fields (`enabled`, `expire`), and treating
+/// any field `want` leaves unset as "don't care".
+fn users_match_ignoring_id(existing: &User, want: &User) -> bool {
+    field_matches(&existing.name, &want.name)
+        && field_matches(&existing.email, &want.email)
+        && field_matches(&existing.alternate_email, &want.alternate_email)
+        && field_matches(&existing.employee_number, &want.employee_number)
+        && field_matches(&existing.title, &want.title)
+        && field_matches(&existing.phone, &want.phone)
+        && field_matches(&existing.location, &want.location)
+        && field_matches(&existing.timezone, &want.timezone)
+        && field_matches(&existing.locale, &want.locale)
+        && field_matches(&existing.role, &want.role)
+        && field_matches(&existing.external_id, &want.external_id)
+}
+
+/// Iterator over every user in the instance, transparently paging through
+/// `GET /v1/users` in `page_size`-sized chunks as the buffer drains.
+pub struct UserPager<'a> {
+    client: &'a super::Client,
+    page_size: u32,
+    offset: u32,
+    buf: std::collections::VecDeque<User>,
+    done: bool,
+}
+
+impl<'a> Iterator for UserPager<'a> {
+    type Item = Result<User, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.is_empty() && !self.done {
+            let page = match self
+                .client
+                .get_users(Some(self.page_size), Some(self.offset))
+            {
+                Ok(page) => page,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            };
+            self.offset += page.len() as u32;
+            if (page.len() as u32) < self.page_size {
+                self.done = true;
+            }
+            self.buf.extend(page);
         }
+        self.buf.pop_front().map(Ok)
     }
 }
 
@@ -105,6 +204,44 @@ impl super::Client {
             .json()?)
     }
 
+    /// Get every active user in the instance: not deleted, enabled, and not
+    /// expired. Pages through the full roster via `users_iter` and filters
+    /// across it, rather than filtering a single `get_users` page — filtering
+    /// after a single `limit`/`offset` page would silently under-fill or skip
+    /// active accounts whenever a page happened to be dominated by inactive
+    /// ones.
+    pub fn get_active_users(&self) -> Result<Vec<User>, Box<dyn Error>> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        self.users_iter(500)
+            .filter(|u| match u {
+                Ok(u) => {
+                    !u.deleted.unwrap_or(false)
+                        && u.enabled.unwrap_or(true)
+                        && u.expire.map_or(true, |expire| expire > now)
+                }
+                Err(_) => true,
+            })
+            .collect()
+    }
+
+    /// Iterate over every user in the instance without managing `limit`/`offset`
+    /// by hand. Fetches `page_size` users per request, yielding them one at a
+    /// time, and stops once a page comes back shorter than `page_size`. A
+    /// `page_size` of `0` is clamped to `1`, since a `0`-sized page can never
+    /// be "shorter than page_size" and would otherwise loop forever.
+    pub fn users_iter(&self, page_size: u32) -> UserPager<'_> {
+        UserPager {
+            client: self,
+            page_size: page_size.max(1),
+            offset: 0,
+            buf: std::collections::VecDeque::new(),
+            done: false,
+        }
+    }
+
     /// Fetch users by email in bulk
     pub fn post_bulk_user_emails(&self, emails: &[String]) -> Result<Vec<User>, Box<dyn Error>> {
         let at = self.get_access_token("user")?;
@@ -118,14 +255,77 @@ impl super::Client {
             .json()?)
     }
 
+    /// Creates each of `users` independently, so one failure (e.g. a
+    /// duplicate email) doesn't abort the rest of the batch.
+    pub fn post_users_bulk(&self, users: &[User]) -> BulkResult {
+        let mut result = BulkResult::default();
+        for user in users {
+            match self.post_user(user.clone()) {
+                Ok(created) => result.succeeded.push(created),
+                Err(e) => result.failed.push((user.clone(), e.to_string())),
+            }
+        }
+        result
+    }
+
+    /// Updates each of `users` independently via `put_user`, keyed by their
+    /// `id`, so one failure doesn't abort the rest of the batch. A user with
+    /// no `id` is recorded as a failure.
+    pub fn put_users_bulk(&self, users: &[User]) -> BulkResult {
+        let mut result = BulkResult::default();
+        for user in users {
+            let outcome = match user.id {
+                Some(id) => self.put_user(&id.to_string(), user.clone()),
+                None => Err(format!("user {:?} has no id to update", user.name).into()),
+            };
+            match outcome {
+                Ok(updated) => result.succeeded.push(updated),
+                Err(e) => result.failed.push((user.clone(), e.to_string())),
+            }
+        }
+        result
+    }
+
+    /// Deletes each of `users` independently via `delete_user`, keyed by
+    /// their `id`, so one failure doesn't abort the rest of the batch. A user
+    /// with no `id` is recorded as a failure.
+    pub fn delete_users_bulk(&self, users: &[User]) -> BulkResult {
+        let mut result = BulkResult::default();
+        for user in users {
+            let outcome: Result<(), Box<dyn Error>> = match user.id {
+                Some(id) => self.delete_user(&id.to_string()),
+                None => Err(format!("user {:?} has no id to delete", user.name).into()),
+            };
+            match outcome {
+                Ok(()) => result.succeeded.push(user.clone()),
+                Err(e) => result.failed.push((user.clone(), e.to_string())),
+            }
+        }
+        result
+    }
+
     /// Creates a new user in your Domo instance.
-    ///
-    /// TODO param sendInvite=true
     pub fn post_user(&self, user: User) -> Result<User, Box<dyn Error>> {
+        self.post_user_with_invite(user, false)
+    }
+
+    /// Creates a new user in your Domo instance, optionally sending them an
+    /// email invitation to set up their own profile instead of requiring the
+    /// caller to supply every field up front.
+    pub fn post_user_with_invite(
+        &self,
+        user: User,
+        send_invite: bool,
+    ) -> Result<User, Box<dyn Error>> {
         let at = self.get_access_token("user")?;
+        let mut q: Vec<(&str, String)> = Vec::new();
+        if send_invite {
+            q.push(("sendInvite", send_invite.to_string()));
+        }
         Ok(self
             .client
             .post(&format!("{}{}", self.host, "/v1/users"))
+            .query(&q)
             .header("Authorization", at)
             .json(&user)
             .send()?
@@ -133,6 +333,17 @@ impl super::Client {
             .json()?)
     }
 
+    /// Invites a new user by email and name, letting them fill in the rest of
+    /// their profile themselves rather than requiring a fully populated `User`.
+    pub fn invite_user(&self, email: &str, name: &str) -> Result<User, Box<dyn Error>> {
+        let user = User {
+            email: Some(email.to_string()),
+            name: Some(name.to_string()),
+            ..User::new()
+        };
+        self.post_user_with_invite(user, true)
+    }
+
     /// Retrieves the details of an existing user.
     ///
     /// Returns a user object if valid user ID was provided. When requesting, if the user ID is related to a user that has been deleted, a subset of the user information will be returned, including a deleted property, which will be true.
@@ -161,6 +372,24 @@ impl super::Client {
             .json()?)
     }
 
+    /// Enables the user's account. Since `put_user` currently requires all
+    /// fields, this reads the existing user first and PUTs back a copy with
+    /// only `enabled` changed.
+    pub fn enable_user(&self, id: &str) -> Result<User, Box<dyn Error>> {
+        let mut user = self.get_user(id)?;
+        user.enabled = Some(true);
+        self.put_user(id, user)
+    }
+
+    /// Disables the user's account, suspending access without deleting it.
+    /// Since `put_user` currently requires all fields, this reads the
+    /// existing user first and PUTs back a copy with only `enabled` changed.
+    pub fn disable_user(&self, id: &str) -> Result<User, Box<dyn Error>> {
+        let mut user = self.get_user(id)?;
+        user.enabled = Some(false);
+        self.put_user(id, user)
+    }
+
     /// Permanently deletes a user from your Domo instance
     /// This is destructive and cannot be reversed.
     pub fn delete_user(&self, id: &str) -> Result<(), Box<dyn Error>> {
@@ -172,4 +401,79 @@ impl super::Client {
             .error_for_status()?;
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Reconciles `desired` against the current roster, matching existing
+    /// users by `external_id` first and falling back to `email`, then issuing
+    /// the minimal set of `post_user`/`put_user`/`delete_user` calls to
+    /// converge. A record with a matching `external_id` is updated in place
+    /// rather than recreated, even if its email changed.
+    ///
+    /// This performs full convergence: `desired` is treated as the complete
+    /// roster, so any current user *not* present in `desired` is permanently
+    /// deleted via `delete_user`, which cannot be reversed. Do not pass a
+    /// partial list expecting only those users to be upserted — that will
+    /// delete everyone else in the instance, including the API user.
+    pub fn sync_users(&self, desired: &[User]) -> Result<SyncReport, Box<dyn Error>> {
+        let current: Vec<User> = self.users_iter(500).collect::<Result<_, _>>()?;
+
+        let mut by_external_id: std::collections::HashMap<&str, &User> =
+            std::collections::HashMap::new();
+        let mut by_email: std::collections::HashMap<&str, &User> = std::collections::HashMap::new();
+        for u in &current {
+            if let Some(ext) = u.external_id.as_deref() {
+                by_external_id.insert(ext, u);
+            }
+            if let Some(email) = u.email.as_deref() {
+                by_email.insert(email, u);
+            }
+        }
+
+        let mut report = SyncReport::default();
+        let mut matched_ids = std::collections::HashSet::new();
+
+        for want in desired {
+            let existing = want
+                .external_id
+                .as_deref()
+                .and_then(|ext| by_external_id.get(ext))
+                .or_else(|| want.email.as_deref().and_then(|email| by_email.get(email)))
+                .copied();
+
+            match existing.and_then(|existing| existing.id.map(|id| (existing, id))) {
+                Some((existing, id)) => {
+                    matched_ids.insert(id);
+                    if users_match_ignoring_id(existing, want) {
+                        report.unchanged.push(id);
+                    } else {
+                        let update = User {
+                            id: Some(id),
+                            ..want.clone()
+                        };
+                        self.put_user(&id.to_string(), update)?;
+                        report.updated.push(id);
+                    }
+                }
+                None => {
+                    // No match, or a matched record came back without an id
+                    // (e.g. the deleted-user subset `User.deleted` describes) —
+                    // either way there's nothing to update in place, so create.
+                    let created = self.post_user(want.clone())?;
+                    if let Some(id) = created.id {
+                        report.created.push(id);
+                    }
+                }
+            }
+        }
+
+        for u in &current {
+            if let Some(id) = u.id {
+                if !matched_ids.contains(&id) {
+                    self.delete_user(&id.to_string())?;
+                    report.deleted.push(id);
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}